@@ -0,0 +1,219 @@
+//! A mock [collector] that asserts on the events recorded by the code
+//! under test, built from a sequence of [`ExpectedEvent`]s. Spans are
+//! accepted but not checked.
+//!
+//! Expectations are consumed from the front of the queue as matching
+//! events are recorded. An expectation with a [repeat range] wider than
+//! "exactly once" is kept at the front of the queue and re-checked
+//! against each subsequent event until it reaches its upper bound, or
+//! until an event fails to match it after its lower bound has been met;
+//! the driver then moves on to the next expectation.
+//!
+//! [collector]: tracing::Collect
+//! [repeat range]: crate::event::ExpectedEvent::times
+use std::{
+    collections::VecDeque,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
+
+use tracing::collect::Interest;
+use tracing_core::{span, Event, Metadata};
+
+use super::event::ExpectedEvent;
+
+/// An expectation still waiting to be matched, along with how many
+/// times it has matched so far.
+struct Pending {
+    expected: ExpectedEvent,
+    seen: usize,
+}
+
+struct Expected {
+    events: VecDeque<Pending>,
+}
+
+/// A mock [collector], constructed with [`mock`].
+///
+/// [collector]: tracing::Collect
+#[derive(Clone)]
+pub struct MockCollector {
+    expected: Arc<Mutex<Expected>>,
+    name: String,
+}
+
+/// A handle to a [`MockCollector`], used to assert that every expected
+/// event was recorded the number of times its expectation required.
+pub struct MockHandle(Arc<Mutex<Expected>>, String);
+
+/// Configures a [`MockCollector`] before it is run.
+///
+/// Constructed with [`mock`].
+pub struct MockCollectorBuilder {
+    expected: VecDeque<Pending>,
+    name: String,
+}
+
+/// Starts building a [`MockCollector`].
+///
+/// ```
+/// use tracing::collect::with_default;
+/// use tracing_mock::{collector, expect};
+///
+/// let event = expect::event().at_level(tracing::Level::INFO);
+///
+/// let (collector, handle) = collector::mock().event(event).run_with_handle();
+///
+/// with_default(collector, || {
+///     tracing::info!("hello");
+/// });
+///
+/// handle.assert_finished();
+/// ```
+pub fn mock() -> MockCollectorBuilder {
+    MockCollectorBuilder {
+        expected: VecDeque::new(),
+        name: std::thread::current().name().unwrap_or("mock").to_string(),
+    }
+}
+
+impl MockCollectorBuilder {
+    /// Overrides the name used to identify this collector in assertion
+    /// failure messages.
+    pub fn named(mut self, name: impl fmt::Display) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Adds an expected event to the end of the queue.
+    pub fn event(mut self, expected: ExpectedEvent) -> Self {
+        self.expected.push_back(Pending { expected, seen: 0 });
+        self
+    }
+
+    /// Consumes the builder, returning the finished [`MockCollector`].
+    pub fn run(self) -> MockCollector {
+        let (collector, _handle) = self.run_with_handle();
+        collector
+    }
+
+    /// Consumes the builder, returning the finished [`MockCollector`]
+    /// along with a [`MockHandle`] that can be used to assert that
+    /// every expectation was satisfied.
+    pub fn run_with_handle(self) -> (MockCollector, MockHandle) {
+        let expected = Arc::new(Mutex::new(Expected {
+            events: self.expected,
+        }));
+        let handle = MockHandle(expected.clone(), self.name.clone());
+        (
+            MockCollector {
+                expected,
+                name: self.name,
+            },
+            handle,
+        )
+    }
+}
+
+impl MockCollector {
+    /// Matches `event` against the expectation at the front of the
+    /// queue, advancing past expectations whose repeat range has
+    /// already been satisfied.
+    fn check_event(&self, event: &Event<'_>) {
+        let mut expected = self.expected.lock().unwrap();
+        loop {
+            let pending = expected.events.front_mut().unwrap_or_else(|| {
+                panic!(
+                    "\n[{}] received event {:?}, but no more events were expected",
+                    self.name, event
+                )
+            });
+
+            let (min, max) = pending.expected.repeat_range();
+            let name = &self.name;
+            let expected_event = &mut pending.expected;
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                expected_event.check(event, || None, name)
+            }));
+
+            match result {
+                Ok(()) => {
+                    pending.seen += 1;
+                    if let Some(max) = max {
+                        assert!(
+                            pending.seen <= max,
+                            "\n[{}] expected {} at most {} time(s), but it was recorded \
+                             at least {} time(s)",
+                            self.name,
+                            pending.expected,
+                            max,
+                            pending.seen
+                        );
+                        if pending.seen == max {
+                            expected.events.pop_front();
+                        }
+                    }
+                    return;
+                }
+                // The event didn't match the expectation at the front of
+                // the queue. If that expectation has already matched
+                // enough times to satisfy its minimum, the event may
+                // belong to whatever comes next — advance and retry.
+                // Otherwise, this really is a mismatch.
+                Err(panic) => {
+                    if pending.seen >= min {
+                        expected.events.pop_front();
+                        continue;
+                    }
+                    panic::resume_unwind(panic);
+                }
+            }
+        }
+    }
+}
+
+impl tracing::Collect for MockCollector {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::always()
+    }
+
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        self.check_event(event);
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+impl MockHandle {
+    /// Asserts that every expected event was recorded at least as many
+    /// times as its repeat range required.
+    pub fn assert_finished(&self) {
+        let expected = self.0.lock().unwrap();
+        for pending in &expected.events {
+            let (min, _max) = pending.expected.repeat_range();
+            assert!(
+                pending.seen >= min,
+                "\n[{}] expected {}, but it was only recorded {} time(s), expected at least {}",
+                self.1,
+                pending.expected,
+                pending.seen,
+                min
+            );
+        }
+    }
+}
@@ -31,24 +31,62 @@ use super::{expect, field, metadata::ExpectedMetadata, span, Parent};
 
 use std::fmt;
 
+/// A closure used to assert on the debug representation of a recorded
+/// field value, as registered by [`ExpectedEvent::with_value_matching`].
+type ValueMatcher = Box<dyn Fn(&dyn fmt::Debug) -> bool + Send + Sync>;
+
 /// An expected event.
 ///
 /// For a detailed description and examples see the documentation for
 /// the methods and the [`event`] module.
 ///
 /// [`event`]: fn@crate::event
-#[derive(Default, Eq, PartialEq)]
+#[derive(Default)]
 pub struct ExpectedEvent {
     pub(super) fields: Option<field::ExpectedFields>,
     pub(super) parent: Option<Parent>,
     pub(super) in_spans: Vec<span::ExpectedSpan>,
     pub(super) metadata: ExpectedMetadata,
+    pub(super) value_matchers: Vec<(String, ValueMatcher)>,
+    pub(super) forbidden_fields: Vec<String>,
+    pub(super) at_least_level: Option<tracing::Level>,
+    pub(super) at_most_level: Option<tracing::Level>,
+    pub(super) min_repeats: Option<usize>,
+    pub(super) max_repeats: Option<usize>,
 }
 
+impl PartialEq for ExpectedEvent {
+    fn eq(&self, other: &Self) -> bool {
+        // `value_matchers` holds closures, which have no meaningful
+        // notion of equality, so two `ExpectedEvent`s are considered
+        // equal if everything but the matchers line up.
+        self.fields == other.fields
+            && self.parent == other.parent
+            && self.in_spans == other.in_spans
+            && self.metadata == other.metadata
+            && self.forbidden_fields == other.forbidden_fields
+            && self.at_least_level == other.at_least_level
+            && self.at_most_level == other.at_most_level
+            && self.min_repeats == other.min_repeats
+            && self.max_repeats == other.max_repeats
+    }
+}
+
+impl Eq for ExpectedEvent {}
+
 pub fn msg(message: impl fmt::Display) -> ExpectedEvent {
     expect::event().with_fields(field::msg(message))
 }
 
+/// Matches an event that must not record a field named `name`.
+///
+/// This is equivalent to `expect::event().without_fields([name])`, and
+/// exists so there's an `expect::no_field` entry point with the same
+/// shape as the other `expect::*` constructors.
+pub fn no_field(name: impl Into<String>) -> ExpectedEvent {
+    expect::event().without_fields([name.into()])
+}
+
 impl ExpectedEvent {
     /// Sets the expected name to match an event.
     ///
@@ -113,6 +151,46 @@ impl ExpectedEvent {
         }
     }
 
+    /// Asserts that the event's level is at least as severe as `level`.
+    ///
+    /// For example, `at_level_at_least(Level::WARN)` matches events
+    /// recorded at `WARN` or `ERROR`, without having to enumerate every
+    /// severity at or above the threshold.
+    ///
+    /// ```
+    /// use tracing::collect::with_default;
+    /// use tracing_mock::{collector, expect};
+    ///
+    /// let event = expect::event().at_level_at_least(tracing::Level::WARN);
+    ///
+    /// let (collector, handle) = collector::mock()
+    ///     .event(event)
+    ///     .run_with_handle();
+    ///
+    /// with_default(collector, || {
+    ///     tracing::error!("something is on fire");
+    /// });
+    ///
+    /// handle.assert_finished();
+    /// ```
+    pub fn at_level_at_least(self, level: tracing::Level) -> Self {
+        Self {
+            at_least_level: Some(level),
+            ..self
+        }
+    }
+
+    /// Asserts that the event's level is no more severe than `level`.
+    ///
+    /// For example, `at_level_at_most(Level::DEBUG)` matches events
+    /// recorded at `DEBUG` or `TRACE`.
+    pub fn at_level_at_most(self, level: tracing::Level) -> Self {
+        Self {
+            at_most_level: Some(level),
+            ..self
+        }
+    }
+
     pub fn with_target<I>(self, target: I) -> Self
     where
         I: Into<String>,
@@ -126,6 +204,84 @@ impl ExpectedEvent {
         }
     }
 
+    /// Adds a predicate that the value recorded for the field `name`
+    /// must satisfy, instead of requiring an exact match.
+    ///
+    /// This is useful when the exact value an event will record isn't
+    /// known ahead of time, but some property of it is — for example,
+    /// that a numeric field falls within a range, or that a string
+    /// contains a particular substring.
+    ///
+    /// Unlike [`with_fields`], which requires the expected value to be
+    /// known up front, `with_value_matching` accepts a closure that is
+    /// handed the recorded value's [`Debug`] representation and returns
+    /// `true` if it's acceptable.
+    ///
+    /// ```
+    /// use tracing::collect::with_default;
+    /// use tracing_mock::{collector, expect};
+    ///
+    /// let event = expect::event().with_value_matching("answer", |value| {
+    ///     format!("{:?}", value) == "42"
+    /// });
+    ///
+    /// let (collector, handle) = collector::mock()
+    ///     .event(event)
+    ///     .run_with_handle();
+    ///
+    /// with_default(collector, || {
+    ///     tracing::info!(answer = 42);
+    /// });
+    ///
+    /// handle.assert_finished();
+    /// ```
+    ///
+    /// [`with_fields`]: fn@Self::with_fields
+    /// [`Debug`]: std::fmt::Debug
+    pub fn with_value_matching<I>(
+        mut self,
+        name: I,
+        matcher: impl Fn(&dyn fmt::Debug) -> bool + Send + Sync + 'static,
+    ) -> Self
+    where
+        I: Into<String>,
+    {
+        self.value_matchers.push((name.into(), Box::new(matcher)));
+        self
+    }
+
+    /// Asserts that the event does *not* record a field with any of the
+    /// given `names`.
+    ///
+    /// This is useful for verifying that a redaction layer actually
+    /// strips a field, or that a span's field isn't incorrectly
+    /// propagated onto one of its child events.
+    ///
+    /// ```
+    /// use tracing::collect::with_default;
+    /// use tracing_mock::{collector, expect};
+    ///
+    /// let event = expect::event().without_fields(["password"]);
+    ///
+    /// let (collector, handle) = collector::mock()
+    ///     .event(event)
+    ///     .run_with_handle();
+    ///
+    /// with_default(collector, || {
+    ///     tracing::info!(username = "ferris");
+    /// });
+    ///
+    /// handle.assert_finished();
+    /// ```
+    pub fn without_fields<I>(mut self, names: impl IntoIterator<Item = I>) -> Self
+    where
+        I: Into<String>,
+    {
+        self.forbidden_fields
+            .extend(names.into_iter().map(Into::into));
+        self
+    }
+
     pub fn with_explicit_parent(self, parent: Option<&str>) -> ExpectedEvent {
         let parent = match parent {
             Some(name) => Parent::Explicit(name.into()),
@@ -154,12 +310,46 @@ impl ExpectedEvent {
             self,
             event
         );
+
+        if let Some(least_severe) = self.at_least_level {
+            assert!(
+                meta.level() <= &least_severe,
+                "[{}] expected {} to be at least as severe as {:?}, but actual level was {:?}",
+                collector_name,
+                self,
+                least_severe,
+                meta.level()
+            );
+        }
+
+        if let Some(most_severe) = self.at_most_level {
+            assert!(
+                meta.level() >= &most_severe,
+                "[{}] expected {} to be no more severe than {:?}, but actual level was {:?}",
+                collector_name,
+                self,
+                most_severe,
+                meta.level()
+            );
+        }
+
         if let Some(ref mut expected_fields) = self.fields {
             let mut checker = expected_fields.checker(name, collector_name);
             event.record(&mut checker);
             checker.finish();
         }
 
+        if !self.value_matchers.is_empty() || !self.forbidden_fields.is_empty() {
+            let mut visitor = FieldAssertionVisitor::new(
+                &self.value_matchers,
+                &self.forbidden_fields,
+                name,
+                collector_name,
+            );
+            event.record(&mut visitor);
+            visitor.finish();
+        }
+
         if let Some(ref expected_parent) = self.parent {
             let actual_parent = get_parent_name();
             expected_parent.check_parent_name(
@@ -181,6 +371,158 @@ impl ExpectedEvent {
     pub fn scope_mut(&mut self) -> &mut [span::ExpectedSpan] {
         &mut self.in_spans[..]
     }
+
+    /// Asserts that a matching event occurs exactly `count` times.
+    ///
+    /// By default, an expectation is consumed the first time a matching
+    /// event is recorded. Calling `times` instead tells the
+    /// [`collector`](mod@crate::collector) to keep matching this same
+    /// expectation against consecutive events until it has been seen
+    /// `count` times, and to report a mismatch at
+    /// [`assert_finished`](crate::collector::MockHandle::assert_finished)
+    /// if the observed count falls short when the test ends.
+    ///
+    /// This is useful for asserting on retry loops or batched logging,
+    /// where the exact ordering of events is known but the number of
+    /// times an event is emitted is what matters.
+    ///
+    /// ```
+    /// use tracing::collect::with_default;
+    /// use tracing_mock::{collector, expect};
+    ///
+    /// let event = expect::event().at_level(tracing::Level::INFO).times(3);
+    ///
+    /// let (collector, handle) = collector::mock()
+    ///     .event(event)
+    ///     .run_with_handle();
+    ///
+    /// with_default(collector, || {
+    ///     for _ in 0..3 {
+    ///         tracing::info!("retrying");
+    ///     }
+    /// });
+    ///
+    /// handle.assert_finished();
+    /// ```
+    pub fn times(self, count: usize) -> Self {
+        Self {
+            min_repeats: Some(count),
+            max_repeats: Some(count),
+            ..self
+        }
+    }
+
+    /// Asserts that a matching event occurs at least `min` times.
+    ///
+    /// See [`times`](Self::times) for details on how repeated
+    /// expectations are matched.
+    pub fn at_least(self, min: usize) -> Self {
+        Self {
+            min_repeats: Some(min),
+            ..self
+        }
+    }
+
+    /// Asserts that a matching event occurs at most `max` times.
+    ///
+    /// See [`times`](Self::times) for details on how repeated
+    /// expectations are matched.
+    pub fn at_most(self, max: usize) -> Self {
+        Self {
+            max_repeats: Some(max),
+            ..self
+        }
+    }
+
+    /// Returns the inclusive range of times this expectation is allowed
+    /// to match, defaulting to exactly once.
+    ///
+    /// Consulted by the [`collector`](mod@crate::collector)'s driver,
+    /// which keeps matching this same expectation against consecutive
+    /// events until it reaches the upper bound, or until an event fails
+    /// to match once the lower bound has been met, before moving on to
+    /// the next expectation in the queue.
+    pub(crate) fn repeat_range(&self) -> (usize, Option<usize>) {
+        match (self.min_repeats, self.max_repeats) {
+            // Neither bound was set: the expectation matches exactly once,
+            // as it did before repeat counts existed.
+            (None, None) => (1, Some(1)),
+            (min, max) => (min.unwrap_or(0), max),
+        }
+    }
+}
+
+/// A single pass over an event's recorded fields that checks both of an
+/// [`ExpectedEvent`]'s field-level assertions: that every registered
+/// [`ValueMatcher`] saw and accepted its field, and that none of the
+/// `forbidden_fields` were recorded.
+///
+/// A matcher that never saw its field recorded is just as much a
+/// failure as one whose predicate returned `false`, so `finish` must be
+/// called once the event has been fully visited to catch fields that
+/// were expected but never showed up (e.g. because they were redacted).
+struct FieldAssertionVisitor<'a> {
+    matchers: &'a [(String, ValueMatcher)],
+    seen_matchers: std::collections::HashSet<&'a str>,
+    forbidden: &'a [String],
+    event_name: &'a str,
+    collector_name: &'a str,
+}
+
+impl<'a> FieldAssertionVisitor<'a> {
+    fn new(
+        matchers: &'a [(String, ValueMatcher)],
+        forbidden: &'a [String],
+        event_name: &'a str,
+        collector_name: &'a str,
+    ) -> Self {
+        Self {
+            matchers,
+            seen_matchers: std::collections::HashSet::new(),
+            forbidden,
+            event_name,
+            collector_name,
+        }
+    }
+
+    fn finish(self) {
+        for (name, _) in self.matchers {
+            assert!(
+                self.seen_matchers.contains(name.as_str()),
+                "[{}] expected a field named `{}` on event \"{}\" to match the given \
+                 predicate, but it was never recorded",
+                self.collector_name,
+                name,
+                self.event_name
+            );
+        }
+    }
+}
+
+impl<'a> tracing::field::Visit for FieldAssertionVisitor<'a> {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn fmt::Debug) {
+        if let Some((name, matcher)) = self.matchers.iter().find(|(name, _)| name == field.name()) {
+            self.seen_matchers.insert(name.as_str());
+            assert!(
+                matcher(value),
+                "\n[{}] expected field `{}` on event \"{}\" to match the given predicate, \
+                 but got `{:?}`",
+                self.collector_name,
+                name,
+                self.event_name,
+                value
+            );
+        }
+
+        assert!(
+            !self.forbidden.iter().any(|name| name == field.name()),
+            "\n[{}] expected event \"{}\" to not have a field named `{}`, but got `{:?}`",
+            self.collector_name,
+            self.event_name,
+            field.name(),
+            value
+        );
+    }
 }
 
 impl fmt::Display for ExpectedEvent {
@@ -205,10 +547,35 @@ impl fmt::Debug for ExpectedEvent {
             s.field("level", &format_args!("{:?}", level));
         }
 
+        if let Some(ref level) = self.at_least_level {
+            s.field("at_least_level", &format_args!("{:?}", level));
+        }
+
+        if let Some(ref level) = self.at_most_level {
+            s.field("at_most_level", &format_args!("{:?}", level));
+        }
+
+        if self.min_repeats.is_some() || self.max_repeats.is_some() {
+            s.field("repeat_range", &format_args!("{:?}", self.repeat_range()));
+        }
+
         if let Some(ref fields) = self.fields {
             s.field("fields", fields);
         }
 
+        if !self.value_matchers.is_empty() {
+            let names: Vec<&str> = self
+                .value_matchers
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect();
+            s.field("value_matchers", &names);
+        }
+
+        if !self.forbidden_fields.is_empty() {
+            s.field("forbidden_fields", &self.forbidden_fields);
+        }
+
         if let Some(ref parent) = self.parent {
             s.field("parent", &format_args!("{:?}", parent));
         }